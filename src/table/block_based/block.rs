@@ -0,0 +1,131 @@
+use super::options::DataBlockIndexType;
+use crate::util::{decode_fixed_uint32, get_var_uint32, get_var_uint64, put_varint64varint64};
+use std::collections::HashSet;
+
+const HASH_INDEX_FLAG: u32 = 1 << 31;
+const VALUE_DELTA_ENCODING_FLAG: u32 = 1 << 30;
+const NUM_RESTARTS_MASK: u32 = (1 << 30) - 1;
+
+/// Packs a data block's footer: which index type was used (plain binary search vs.
+/// binary search backed by a hash index), whether values were value-delta encoded,
+/// and the restart point count, all into a single `u32`.
+pub fn pack_index_type_and_num_restarts(
+    index_type: DataBlockIndexType,
+    use_value_delta_encoding: bool,
+    num_restarts: u32,
+) -> u32 {
+    assert_eq!(
+        num_restarts & !NUM_RESTARTS_MASK,
+        0,
+        "num_restarts must fit in the footer's restart-count bits"
+    );
+    let mut footer = num_restarts;
+    if index_type == DataBlockIndexType::DataBlockBinaryAndHash {
+        footer |= HASH_INDEX_FLAG;
+    }
+    if use_value_delta_encoding {
+        footer |= VALUE_DELTA_ENCODING_FLAG;
+    }
+    footer
+}
+
+/// The inverse of `pack_index_type_and_num_restarts`.
+pub fn unpack_index_type_and_num_restarts(footer: u32) -> (DataBlockIndexType, bool, u32) {
+    let index_type = if footer & HASH_INDEX_FLAG != 0 {
+        DataBlockIndexType::DataBlockBinaryAndHash
+    } else {
+        DataBlockIndexType::DataBlockBinarySearch
+    };
+    let use_value_delta_encoding = footer & VALUE_DELTA_ENCODING_FLAG != 0;
+    (index_type, use_value_delta_encoding, footer & NUM_RESTARTS_MASK)
+}
+
+/// Linearly decodes every `(key, value)` entry out of a block produced by
+/// `BlockBuilder::finish`, reversing both key-prefix compression and (when the footer's
+/// value-delta-encoding bit is set) the `BlockHandle` size-delta encoding: non-restart
+/// entries store only the handle's size, and `offset` is reconstructed as
+/// `prev_offset + prev_size`.
+///
+/// This only supports the plain binary-search index layout; blocks built with the
+/// binary-search-plus-hash index are not decoded by this helper.
+pub fn decode_entries(block: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let footer = decode_fixed_uint32(&block[block.len() - 4..]);
+    let (index_type, use_value_delta_encoding, num_restarts) = unpack_index_type_and_num_restarts(footer);
+    assert_eq!(
+        index_type,
+        DataBlockIndexType::DataBlockBinarySearch,
+        "decode_entries does not support the hash-augmented index layout"
+    );
+    let restarts_start = block.len() - 4 - (num_restarts as usize) * 4;
+    let restart_offsets: HashSet<u32> = (0..num_restarts)
+        .map(|i| {
+            let start = restarts_start + i as usize * 4;
+            decode_fixed_uint32(&block[start..start + 4])
+        })
+        .collect();
+
+    let mut entries = vec![];
+    let mut pos = 0usize;
+    let mut last_key: Vec<u8> = vec![];
+    let mut last_handle_offset = 0u64;
+    let mut last_handle_size = 0u64;
+    while pos < restarts_start {
+        let is_restart = restart_offsets.contains(&(pos as u32));
+        let shared = get_var_uint32(&block[pos..], &mut pos).unwrap() as usize;
+        let non_shared = get_var_uint32(&block[pos..], &mut pos).unwrap() as usize;
+        let value_len = get_var_uint32(&block[pos..], &mut pos).unwrap() as usize;
+
+        let mut key = last_key[..shared].to_vec();
+        key.extend_from_slice(&block[pos..pos + non_shared]);
+        pos += non_shared;
+
+        let value_bytes = &block[pos..pos + value_len];
+        pos += value_len;
+
+        let value = if use_value_delta_encoding {
+            let mut vpos = 0;
+            if is_restart {
+                let offset = get_var_uint64(value_bytes, &mut vpos).unwrap();
+                let size = get_var_uint64(&value_bytes[vpos..], &mut vpos).unwrap();
+                last_handle_offset = offset;
+                last_handle_size = size;
+                value_bytes.to_vec()
+            } else {
+                let size = get_var_uint64(value_bytes, &mut vpos).unwrap();
+                let offset = last_handle_offset + last_handle_size;
+                last_handle_offset = offset;
+                last_handle_size = size;
+                let mut full_handle = vec![];
+                put_varint64varint64(&mut full_handle, offset, size);
+                full_handle
+            }
+        } else {
+            value_bytes.to_vec()
+        };
+
+        last_key = key.clone();
+        entries.push((key, value));
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_unpack_round_trip() {
+        for index_type in [
+            DataBlockIndexType::DataBlockBinarySearch,
+            DataBlockIndexType::DataBlockBinaryAndHash,
+        ] {
+            for use_value_delta_encoding in [false, true] {
+                let footer = pack_index_type_and_num_restarts(index_type, use_value_delta_encoding, 7);
+                assert_eq!(
+                    unpack_index_type_and_num_restarts(footer),
+                    (index_type, use_value_delta_encoding, 7)
+                );
+            }
+        }
+    }
+}
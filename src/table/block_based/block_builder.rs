@@ -3,13 +3,13 @@ use super::options::DataBlockIndexType;
 use crate::common::format::extract_user_key;
 use crate::table::block_based::block::pack_index_type_and_num_restarts;
 use crate::table::format::MAX_BLOCK_SIZE_SUPPORTED_BY_HASH_INDEX;
-use crate::util::{difference_offset, encode_var_uint32};
+use crate::util::{difference_offset, encode_var_uint32, encode_var_uint64, get_var_uint64};
+use bytes::{Bytes, BytesMut};
 
 pub const DEFAULT_HASH_TABLE_UTIL_RATIO: f64 = 0.75;
 
-// TODO: support encode delta value encoding
 pub struct BlockBuilder {
-    buff: Vec<u8>,
+    buff: BytesMut,
     restarts: Vec<u32>,
     last_key: Vec<u8>,
     count: usize,
@@ -17,6 +17,13 @@ pub struct BlockBuilder {
     use_delta_encoding: bool,
     hash_index_builder: DataBlockHashIndexBuilder,
     estimate: usize,
+
+    // Index/handle blocks hold `(offset, size)` pairs whose offsets are strictly
+    // contiguous, so non-restart entries only need to store the size; `offset` is
+    // reconstructed by the reader as `prev_offset + prev_size`.
+    use_value_delta_encoding: bool,
+    last_handle_offset: u64,
+    last_handle_size: u64,
 }
 
 impl BlockBuilder {
@@ -25,13 +32,31 @@ impl BlockBuilder {
         use_delta_encoding: bool,
         index_type: DataBlockIndexType,
         data_block_hash_table_util_ratio: f64,
+    ) -> BlockBuilder {
+        Self::new_with_value_delta_encoding(
+            block_restart_interval,
+            use_delta_encoding,
+            index_type,
+            data_block_hash_table_util_ratio,
+            false,
+        )
+    }
+
+    /// Like `new`, but additionally enables value-delta encoding for index/handle
+    /// blocks whose values are `(offset, size)` `BlockHandle` pairs.
+    pub fn new_with_value_delta_encoding(
+        block_restart_interval: usize,
+        use_delta_encoding: bool,
+        index_type: DataBlockIndexType,
+        data_block_hash_table_util_ratio: f64,
+        use_value_delta_encoding: bool,
     ) -> BlockBuilder {
         let mut hash_index_builder = DataBlockHashIndexBuilder::default();
         if index_type == DataBlockIndexType::DataBlockBinaryAndHash {
             hash_index_builder.init(data_block_hash_table_util_ratio);
         }
         BlockBuilder {
-            buff: vec![],
+            buff: BytesMut::new(),
             block_restart_interval,
             use_delta_encoding,
             hash_index_builder,
@@ -39,6 +64,9 @@ impl BlockBuilder {
             estimate: std::mem::size_of::<u32>() * 2,
             count: 0,
             last_key: vec![],
+            use_value_delta_encoding,
+            last_handle_offset: 0,
+            last_handle_size: 0,
         }
     }
 
@@ -48,17 +76,49 @@ impl BlockBuilder {
 
     pub fn add(&mut self, key: &[u8], value: &[u8]) {
         let mut shared = 0;
-        if self.count >= self.block_restart_interval {
+        // The very first entry lands at the block's initial (pre-seeded) restart
+        // point, offset 0, even though `count` hasn't yet crossed the restart
+        // interval.
+        let is_restart = self.count >= self.block_restart_interval || self.is_empty();
+        if is_restart && !self.is_empty() {
             self.restarts.push(self.buff.len() as u32);
             self.estimate += std::mem::size_of::<u32>();
             self.count = 0;
             if self.use_delta_encoding {
-                self.last_key = key.to_vec();
+                self.last_key.clear();
+                self.last_key.extend_from_slice(key);
             }
         } else if self.use_delta_encoding {
             shared = difference_offset(&self.last_key, key) as usize;
-            self.last_key = key.to_vec();
+            self.last_key.truncate(shared);
+            self.last_key.extend_from_slice(&key[shared..]);
         }
+
+        let mut delta_value_buf: [u8; 10] = [0u8; 10];
+        let value = if self.use_value_delta_encoding {
+            let mut pos = 0;
+            let handle_offset = get_var_uint64(value, &mut pos)
+                .expect("value-delta encoding requires a BlockHandle-shaped (offset, size) value");
+            let handle_size = get_var_uint64(&value[pos..], &mut pos)
+                .expect("value-delta encoding requires a BlockHandle-shaped (offset, size) value");
+            let encoded = if is_restart {
+                value
+            } else {
+                debug_assert_eq!(
+                    handle_offset,
+                    self.last_handle_offset + self.last_handle_size,
+                    "block handles must be contiguous to use value-delta encoding"
+                );
+                let len = encode_var_uint64(&mut delta_value_buf, handle_size);
+                &delta_value_buf[..len]
+            };
+            self.last_handle_offset = handle_offset;
+            self.last_handle_size = handle_size;
+            encoded
+        } else {
+            value
+        };
+
         let mut tmp: [u8; 15] = [0u8; 15];
         let non_shared = key.len() - shared;
         let curr_size = self.buff.len();
@@ -77,7 +137,9 @@ impl BlockBuilder {
         self.count += 1;
     }
 
-    pub fn finish(&mut self) -> &[u8] {
+    /// Finishes the block and hands its bytes out as a refcounted `Bytes`, so the
+    /// cache and table reader can share the same allocation instead of copying it.
+    pub fn finish(&mut self) -> Bytes {
         for i in self.restarts.iter() {
             self.buff.extend_from_slice(&i.to_le_bytes());
         }
@@ -89,9 +151,13 @@ impl BlockBuilder {
         } else {
             DataBlockIndexType::DataBlockBinarySearch
         };
-        let block_footer = pack_index_type_and_num_restarts(index_type, self.restarts.len() as u32);
+        let block_footer = pack_index_type_and_num_restarts(
+            index_type,
+            self.use_value_delta_encoding,
+            self.restarts.len() as u32,
+        );
         self.buff.extend_from_slice(&block_footer.to_le_bytes());
-        &self.buff
+        self.buff.split().freeze()
     }
 
     pub fn clear(&mut self) {
@@ -101,6 +167,8 @@ impl BlockBuilder {
         self.estimate = std::mem::size_of::<u32>() * 2;
         self.count = 0;
         self.last_key.clear();
+        self.last_handle_offset = 0;
+        self.last_handle_size = 0;
         if self.hash_index_builder.valid() {
             self.hash_index_builder.clear();
         }
@@ -227,4 +295,51 @@ mod tests {
             iter.next();
         }
     }
+
+    #[test]
+    fn test_value_delta_encoding_shrinks_contiguous_handles() {
+        use crate::util::put_varint64varint64;
+
+        // Contiguous BlockHandle-shaped (offset, size) values, as an index block would
+        // produce them: each entry starts right where the previous one ended.
+        let handles = [(0u64, 100u64), (100, 80), (180, 120), (300, 64)];
+        let mut encoded_handles = vec![];
+        for (offset, size) in handles.iter() {
+            let mut value = vec![];
+            put_varint64varint64(&mut value, *offset, *size);
+            encoded_handles.push(value);
+        }
+
+        let mut with_delta = BlockBuilder::new_with_value_delta_encoding(
+            100,
+            false,
+            DataBlockIndexType::DataBlockBinarySearch,
+            0.0,
+            true,
+        );
+        let mut without_delta = BlockBuilder::new_with_value_delta_encoding(
+            100,
+            false,
+            DataBlockIndexType::DataBlockBinarySearch,
+            0.0,
+            false,
+        );
+        for (i, value) in encoded_handles.iter().enumerate() {
+            let key = format!("key{:04}", i);
+            with_delta.add(key.as_bytes(), value);
+            without_delta.add(key.as_bytes(), value);
+        }
+        // Only the first (restart) entry pays for the full (offset, size) pair; the
+        // rest store just the size delta, so the block with delta encoding is smaller.
+        assert!(with_delta.current_size_estimate() < without_delta.current_size_estimate());
+
+        // The footer's value-delta bit lets a reader reconstruct every full (offset,
+        // size) handle from the size-only non-restart entries.
+        let data = with_delta.finish();
+        let decoded = crate::table::block_based::block::decode_entries(&data);
+        assert_eq!(decoded.len(), handles.len());
+        for ((_, value), expected) in decoded.iter().zip(encoded_handles.iter()) {
+            assert_eq!(value, expected);
+        }
+    }
 }
@@ -0,0 +1,58 @@
+use crate::util::checksum::ChecksumType;
+use std::io;
+
+/// Fixed-size table footer. Today this holds nothing but the whole-block
+/// `ChecksumType` used for every block in the table, written once by
+/// `BlockBasedTableBuilder::footer` and read back once by
+/// `BlockBasedTableReader::open` before any block is parsed. Recovering the
+/// checksum type from the footer (rather than trusting whatever `ChecksumType`
+/// happens to be set on the reader's options) is what lets `digest_len` — and
+/// therefore the trailer's digest boundary — be computed correctly regardless of
+/// what the reader's options default to.
+pub const FOOTER_LEN: usize = 1;
+
+/// Encodes the table footer for a table built with `checksum`.
+pub fn encode(checksum: ChecksumType) -> [u8; FOOTER_LEN] {
+    [checksum as u8]
+}
+
+/// Decodes the `ChecksumType` recorded in `footer`, which must be exactly
+/// `FOOTER_LEN` bytes.
+pub fn decode(footer: &[u8]) -> io::Result<ChecksumType> {
+    if footer.len() != FOOTER_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("table footer is {} bytes, expected {}", footer.len(), FOOTER_LEN),
+        ));
+    }
+    ChecksumType::from_u8(footer[0])
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("unknown checksum type {} in table footer", footer[0])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_every_checksum_type() {
+        for checksum in [
+            ChecksumType::NoChecksum,
+            ChecksumType::CRC32c,
+            ChecksumType::XXHash,
+            ChecksumType::XXH3,
+        ] {
+            assert_eq!(decode(&encode(checksum)).unwrap(), checksum);
+        }
+    }
+
+    #[test]
+    fn test_unknown_checksum_byte_is_rejected() {
+        assert!(decode(&[0xff]).is_err());
+    }
+
+    #[test]
+    fn test_wrong_length_is_rejected() {
+        assert!(decode(&[]).is_err());
+        assert!(decode(&[0, 0]).is_err());
+    }
+}
@@ -1,17 +1,23 @@
+mod block;
 mod block_builder;
 mod block_table;
 mod bloom;
+mod compression;
 mod data_block_hash_index_builder;
 mod filter_block_builder;
+mod footer;
 mod full_filter_block_builder;
 mod index_builder;
 mod meta_block;
 mod options;
+mod string_dict_builder;
 mod table_builder;
 mod table_builder_factory;
 mod table_reader;
 
+pub use compression::{BlockCompressor, CompressionId, CompressorRegistry};
 pub use filter_block_builder::FilterBuilderFactory;
 pub use full_filter_block_builder::FullFilterBlockFactory;
-pub use options::BlockBasedTableOptions;
+pub use options::{BlockBasedTableOptions, DataBlockIndexType};
+pub use string_dict_builder::{StringDict, StringDictBuilder};
 pub use table_builder_factory::BlockBasedTableFactory;
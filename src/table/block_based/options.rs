@@ -0,0 +1,50 @@
+use crate::table::block_based::compression::{CompressionId, CompressorRegistry, NO_COMPRESSION};
+use crate::util::checksum::ChecksumType;
+use std::sync::Arc;
+
+/// How a data block's restart index is encoded: plain binary search over the restart
+/// points, or binary search backed by an additional hash index for point lookups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataBlockIndexType {
+    DataBlockBinarySearch,
+    DataBlockBinaryAndHash,
+}
+
+#[derive(Clone)]
+pub struct BlockBasedTableOptions {
+    pub block_size: usize,
+    pub block_restart_interval: usize,
+    pub index_block_restart_interval: usize,
+    pub data_block_index_type: DataBlockIndexType,
+    pub data_block_hash_table_util_ratio: f64,
+
+    /// Id of the compressor used for newly-written blocks; looked up in `compressors`.
+    /// `NO_COMPRESSION` disables compression entirely.
+    pub compression: CompressionId,
+    /// Registry of `BlockCompressor`s available to the table builder/reader, seeded
+    /// with the built-in Snappy/Zlib/LZ4/ZSTD compressors. Callers can register custom
+    /// compression ids before opening a table.
+    pub compressors: Arc<CompressorRegistry>,
+
+    /// Whole-block checksum algorithm used by default when building a table, and
+    /// applied uniformly to every block in it. `BlockBasedTableBuilder::footer` writes
+    /// this into the table footer once per table, and `BlockBasedTableReader::open`
+    /// reads it back from there rather than trusting this field on the reader's own
+    /// options — only the builder side needs to set this deliberately.
+    pub checksum: ChecksumType,
+}
+
+impl Default for BlockBasedTableOptions {
+    fn default() -> Self {
+        Self {
+            block_size: 4 * 1024,
+            block_restart_interval: 16,
+            index_block_restart_interval: 1,
+            data_block_index_type: DataBlockIndexType::DataBlockBinarySearch,
+            data_block_hash_table_util_ratio: 0.75,
+            compression: NO_COMPRESSION,
+            compressors: Arc::new(CompressorRegistry::new()),
+            checksum: ChecksumType::CRC32c,
+        }
+    }
+}
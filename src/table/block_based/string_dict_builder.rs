@@ -0,0 +1,310 @@
+use crate::util::{difference_offset, get_var_uint32, put_var_uint32};
+
+/// Number of entries that share a common "head" entry before front-coding restarts,
+/// mirroring `BlockBuilder`'s restart interval but for a sorted string dictionary
+/// rather than a data block.
+const DEFAULT_DICT_BLOCK_SIZE: usize = 16;
+
+/// A front-coded, immutable dictionary mapping a dense id range to sorted strings.
+///
+/// The input is partitioned into fixed-size blocks of `block_size` entries. The first
+/// entry of each block is stored in full; every later entry in the block is stored as
+/// `vbyte(shared_prefix_len) + vbyte(suffix_len) + suffix_bytes`, sharing a prefix with
+/// the entry right before it. A separate `block_heads` offsets array lets lookups
+/// binary-search the block that could contain a key before decoding forward
+/// sequentially within it.
+pub struct StringDictBuilder {
+    block_size: usize,
+    buff: Vec<u8>,
+    block_heads: Vec<u32>,
+    last_key: Vec<u8>,
+    count_in_block: usize,
+}
+
+impl StringDictBuilder {
+    pub fn new() -> Self {
+        Self::with_block_size(DEFAULT_DICT_BLOCK_SIZE)
+    }
+
+    pub fn with_block_size(block_size: usize) -> Self {
+        Self {
+            block_size,
+            buff: vec![],
+            block_heads: vec![],
+            last_key: vec![],
+            count_in_block: 0,
+        }
+    }
+
+    /// Appends `key` as the next id in sorted order. The caller is responsible for
+    /// feeding keys in strictly ascending order; ids are assigned densely starting
+    /// from 0 in insertion order.
+    pub fn add(&mut self, key: &[u8]) {
+        if self.count_in_block == 0 {
+            self.block_heads.push(self.buff.len() as u32);
+            put_var_uint32(&mut self.buff, key.len() as u32);
+            self.buff.extend_from_slice(key);
+        } else {
+            let shared = difference_offset(&self.last_key, key);
+            let suffix = &key[shared..];
+            put_var_uint32(&mut self.buff, shared as u32);
+            put_var_uint32(&mut self.buff, suffix.len() as u32);
+            self.buff.extend_from_slice(suffix);
+        }
+        self.last_key.clear();
+        self.last_key.extend_from_slice(key);
+        self.count_in_block += 1;
+        if self.count_in_block >= self.block_size {
+            self.count_in_block = 0;
+        }
+    }
+
+    pub fn finish(self) -> StringDict {
+        StringDict {
+            block_size: self.block_size,
+            buff: self.buff,
+            block_heads: self.block_heads,
+        }
+    }
+}
+
+impl Default for StringDictBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The finished, read-only dictionary produced by `StringDictBuilder`.
+pub struct StringDict {
+    block_size: usize,
+    buff: Vec<u8>,
+    block_heads: Vec<u32>,
+}
+
+impl StringDict {
+    pub fn len(&self) -> usize {
+        if self.block_heads.is_empty() {
+            0
+        } else {
+            (self.block_heads.len() - 1) * self.block_size + self.last_block_len()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.block_heads.is_empty()
+    }
+
+    fn last_block_len(&self) -> usize {
+        // Recomputed by walking the final block; cheap since dict blocks are small.
+        let mut iter = self.iterate_block(self.block_heads.len() - 1);
+        let mut n = 0;
+        while iter.next().is_some() {
+            n += 1;
+        }
+        n
+    }
+
+    /// Decodes the `id`-th string. `O(block_size)` within the containing block.
+    /// Returns `None` for an out-of-range `id` instead of panicking.
+    pub fn lookup_by_id(&self, id: u64) -> Option<Vec<u8>> {
+        let block = (id as usize) / self.block_size;
+        if block >= self.block_heads.len() {
+            return None;
+        }
+        let within = (id as usize) % self.block_size;
+        let mut iter = self.iterate_block(block);
+        let mut entry = None;
+        for _ in 0..=within {
+            entry = iter.next();
+        }
+        entry
+    }
+
+    /// Binary-searches the block heads, then scans forward within the chosen block to
+    /// find `key`, returning its dense id if present.
+    pub fn lookup_id(&self, key: &[u8]) -> Option<u64> {
+        if self.block_heads.is_empty() {
+            return None;
+        }
+        // Find the last block whose head entry is <= key.
+        let mut lo = 0usize;
+        let mut hi = self.block_heads.len();
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let head = self.block_head_key(mid);
+            if head.as_slice() <= key {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        if lo == 0 {
+            return None;
+        }
+        let block = lo - 1;
+        let mut iter = self.iterate_block(block);
+        let mut idx = 0u64;
+        while let Some(entry) = iter.next() {
+            if entry.as_slice() == key {
+                return Some(block as u64 * self.block_size as u64 + idx);
+            }
+            if entry.as_slice() > key {
+                return None;
+            }
+            idx += 1;
+        }
+        None
+    }
+
+    fn block_head_key(&self, block: usize) -> Vec<u8> {
+        let offset = self.block_heads[block] as usize;
+        let mut pos = offset;
+        let len = get_var_uint32(&self.buff[pos..], &mut pos).unwrap() as usize;
+        self.buff[pos..pos + len].to_vec()
+    }
+
+    fn iterate_block(&self, block: usize) -> StringDictBlockIterator<'_> {
+        StringDictBlockIterator {
+            dict: self,
+            offset: self.block_heads[block] as usize,
+            limit: self
+                .block_heads
+                .get(block + 1)
+                .copied()
+                .map(|o| o as usize)
+                .unwrap_or(self.buff.len()),
+            last_key: Vec::new(),
+            first: true,
+        }
+    }
+
+    pub fn iter(&self) -> StringDictIterator<'_> {
+        StringDictIterator {
+            dict: self,
+            block: 0,
+            within: StringDictBlockIterator {
+                dict: self,
+                offset: 0,
+                limit: 0,
+                last_key: Vec::new(),
+                first: true,
+            },
+            started: false,
+        }
+    }
+}
+
+struct StringDictBlockIterator<'a> {
+    dict: &'a StringDict,
+    offset: usize,
+    limit: usize,
+    last_key: Vec<u8>,
+    first: bool,
+}
+
+impl<'a> Iterator for StringDictBlockIterator<'a> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Vec<u8>> {
+        if self.offset >= self.limit {
+            return None;
+        }
+        let mut pos = self.offset;
+        if self.first {
+            let len = get_var_uint32(&self.dict.buff[pos..], &mut pos).unwrap() as usize;
+            let key = self.dict.buff[pos..pos + len].to_vec();
+            self.offset = pos + len;
+            self.last_key = key.clone();
+            self.first = false;
+            Some(key)
+        } else {
+            let shared = get_var_uint32(&self.dict.buff[pos..], &mut pos).unwrap() as usize;
+            let suffix_len = get_var_uint32(&self.dict.buff[pos..], &mut pos).unwrap() as usize;
+            let suffix = &self.dict.buff[pos..pos + suffix_len];
+            let mut key = self.last_key[..shared].to_vec();
+            key.extend_from_slice(suffix);
+            self.offset = pos + suffix_len;
+            self.last_key = key.clone();
+            Some(key)
+        }
+    }
+}
+
+/// Iterates every string in the dictionary in ascending (id) order.
+pub struct StringDictIterator<'a> {
+    dict: &'a StringDict,
+    block: usize,
+    within: StringDictBlockIterator<'a>,
+    started: bool,
+}
+
+impl<'a> Iterator for StringDictIterator<'a> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Vec<u8>> {
+        if !self.started {
+            if self.dict.block_heads.is_empty() {
+                return None;
+            }
+            self.within = self.dict.iterate_block(self.block);
+            self.started = true;
+        }
+        loop {
+            if let Some(entry) = self.within.next() {
+                return Some(entry);
+            }
+            self.block += 1;
+            if self.block >= self.dict.block_heads.len() {
+                return None;
+            }
+            self.within = self.dict.iterate_block(self.block);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(keys: &[&[u8]], block_size: usize) -> StringDict {
+        let mut builder = StringDictBuilder::with_block_size(block_size);
+        for k in keys {
+            builder.add(k);
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn test_lookup_by_id_and_by_key() {
+        let keys: Vec<&[u8]> = vec![
+            b"apple", b"applet", b"apricot", b"banana", b"bandana", b"bandit", b"candy",
+        ];
+        let dict = build(&keys, 3);
+        for (id, key) in keys.iter().enumerate() {
+            assert_eq!(dict.lookup_by_id(id as u64).unwrap(), key.to_vec());
+            assert_eq!(dict.lookup_id(key).unwrap(), id as u64);
+        }
+        assert!(dict.lookup_id(b"missing").is_none());
+        assert_eq!(dict.len(), keys.len());
+    }
+
+    #[test]
+    fn test_lookup_by_id_out_of_range_returns_none() {
+        let keys: Vec<&[u8]> = vec![b"apple", b"banana", b"candy"];
+        let dict = build(&keys, 2);
+        assert!(dict.lookup_by_id(keys.len() as u64).is_none());
+        assert!(dict.lookup_by_id(u64::MAX).is_none());
+
+        let empty = build(&[], 2);
+        assert!(empty.lookup_by_id(0).is_none());
+    }
+
+    #[test]
+    fn test_iterator_yields_keys_in_order() {
+        let keys: Vec<&[u8]> = vec![b"aa", b"ab", b"ac", b"ba", b"bb"];
+        let dict = build(&keys, 2);
+        let collected: Vec<Vec<u8>> = dict.iter().collect();
+        let expected: Vec<Vec<u8>> = keys.iter().map(|k| k.to_vec()).collect();
+        assert_eq!(collected, expected);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::table::block_based::footer;
+use crate::table::block_based::options::BlockBasedTableOptions;
+use crate::util::checksum;
+use std::sync::Arc;
+
+/// Compresses a just-finished block with the configured compressor and appends the
+/// standard trailer: a 1-byte compression id, the 4-byte little-endian length of the
+/// *uncompressed* block (so the reader never has to guess a decompression buffer
+/// size), and the digest produced by the table's configured `ChecksumType`, computed
+/// over `payload ++ compression_id ++ raw_len`. Folding `raw_len` into the checksummed
+/// bytes means a corrupted length field is caught by verification instead of being
+/// handed to the compressor unauthenticated. The digest width (0/4/8 bytes) is implied
+/// by the checksum type recorded once in the table footer, so every block in a table
+/// shares the same trailer length.
+///
+/// If the compressed representation is not smaller than `raw_block`, the block is
+/// stored uncompressed with compression id 0 instead, so reads never pay a
+/// decompression cost for blocks that didn't actually shrink.
+pub fn compress_block(raw_block: &[u8], options: &BlockBasedTableOptions) -> Vec<u8> {
+    let (compression, payload) = match options.compressors.get(options.compression) {
+        Some(compressor) if options.compression != crate::table::block_based::compression::NO_COMPRESSION => {
+            let compressed = compressor.compress(raw_block);
+            if compressed.len() < raw_block.len() {
+                (options.compression, compressed)
+            } else {
+                (crate::table::block_based::compression::NO_COMPRESSION, raw_block.to_vec())
+            }
+        }
+        _ => (crate::table::block_based::compression::NO_COMPRESSION, raw_block.to_vec()),
+    };
+    let digest_len = options.checksum.digest_len();
+    let mut trailer_meta = [0u8; 5];
+    trailer_meta[0] = compression;
+    trailer_meta[1..].copy_from_slice(&(raw_block.len() as u32).to_le_bytes());
+
+    let mut block = Vec::with_capacity(payload.len() + 5 + digest_len);
+    block.extend_from_slice(&payload);
+    block.extend_from_slice(&trailer_meta);
+    let digest = checksum::compute(options.checksum, &payload, &trailer_meta);
+    block.extend_from_slice(&digest.to_le_bytes()[..digest_len]);
+    block
+}
+
+pub struct BlockBasedTableBuilder {
+    options: Arc<BlockBasedTableOptions>,
+}
+
+impl BlockBasedTableBuilder {
+    pub fn new(options: Arc<BlockBasedTableOptions>) -> Self {
+        Self { options }
+    }
+
+    /// Finalizes a data/index block produced by `BlockBuilder::finish` for writing to
+    /// the SST: compress it (falling back to raw storage if compression doesn't help)
+    /// and append the trailer (compression id, raw length, checksum digest).
+    pub fn finish_block(&self, raw_block: &[u8]) -> Vec<u8> {
+        compress_block(raw_block, &self.options)
+    }
+
+    /// Encodes the table footer, written once per table after its last block.
+    /// Currently just the `ChecksumType` used for every block's trailer digest, so
+    /// `BlockBasedTableReader::open` can recover it rather than requiring the reader's
+    /// options to agree with the writer's out of band.
+    pub fn footer(&self) -> [u8; footer::FOOTER_LEN] {
+        footer::encode(self.options.checksum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::block_based::options::BlockBasedTableOptions;
+
+    #[test]
+    fn test_incompressible_block_falls_back_to_raw() {
+        let options = BlockBasedTableOptions {
+            compression: crate::table::block_based::compression::SNAPPY_COMPRESSION,
+            ..Default::default()
+        };
+        let raw = b"x".to_vec();
+        let block = compress_block(&raw, &options);
+        // payload (1 byte) + compression id (1 byte) + raw len (4 bytes) + CRC32c digest (4 bytes)
+        assert_eq!(block.len(), 10);
+        assert_eq!(block[1], crate::table::block_based::compression::NO_COMPRESSION);
+    }
+
+    #[test]
+    fn test_compressible_block_is_compressed() {
+        let options = BlockBasedTableOptions {
+            compression: crate::table::block_based::compression::SNAPPY_COMPRESSION,
+            ..Default::default()
+        };
+        let raw = b"abcdefgh".repeat(64);
+        let block = compress_block(&raw, &options);
+        assert_eq!(
+            block[block.len() - 9],
+            crate::table::block_based::compression::SNAPPY_COMPRESSION
+        );
+        assert!(block.len() < raw.len());
+    }
+
+    #[test]
+    fn test_checksum_type_controls_trailer_width() {
+        use crate::util::checksum::ChecksumType;
+
+        let raw = b"some block bytes".to_vec();
+        for checksum in [
+            ChecksumType::NoChecksum,
+            ChecksumType::CRC32c,
+            ChecksumType::XXHash,
+            ChecksumType::XXH3,
+        ] {
+            let options = BlockBasedTableOptions {
+                checksum,
+                ..Default::default()
+            };
+            let block = compress_block(&raw, &options);
+            // payload + compression id + raw len + digest
+            assert_eq!(block.len(), raw.len() + 5 + checksum.digest_len());
+        }
+    }
+
+    #[test]
+    fn test_highly_compressible_block_shrinks_past_four_to_one_with_every_compressor() {
+        // A block that compresses far better than a 4:1 ratio, which used to make
+        // `read_block` mis-size its decompression buffer (see table_reader tests for
+        // the read side of this fix).
+        let raw = b"abcdefgh-abcdefgh-abcdefgh-abcdefgh".repeat(50);
+        for compression in [
+            crate::table::block_based::compression::SNAPPY_COMPRESSION,
+            crate::table::block_based::compression::ZLIB_COMPRESSION,
+            crate::table::block_based::compression::LZ4_COMPRESSION,
+            crate::table::block_based::compression::ZSTD_COMPRESSION,
+        ] {
+            let options = BlockBasedTableOptions {
+                compression,
+                ..Default::default()
+            };
+            let block = compress_block(&raw, &options);
+            assert!(block.len() < raw.len() / 4);
+        }
+    }
+}
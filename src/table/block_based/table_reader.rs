@@ -0,0 +1,224 @@
+use crate::table::block_based::footer;
+use crate::table::block_based::options::BlockBasedTableOptions;
+use crate::util::checksum;
+use std::io;
+use std::sync::Arc;
+
+/// Reverses `table_builder::compress_block`: validates the trailer's digest (computed
+/// over the payload, the compression id, and the raw-length field, so a corrupted
+/// length can't slip past verification) against the table's configured `ChecksumType`,
+/// looks up the compressor for the stored compression id and decompresses the payload
+/// (a no-op copy when the id is `NO_COMPRESSION`).
+pub fn read_block(block_with_trailer: &[u8], options: &BlockBasedTableOptions) -> io::Result<Vec<u8>> {
+    let digest_len = options.checksum.digest_len();
+    // 1-byte compression id + 4-byte raw (uncompressed) length + digest.
+    let trailer_size = 5 + digest_len;
+    if block_with_trailer.len() < trailer_size {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "block truncated"));
+    }
+    let split = block_with_trailer.len() - trailer_size;
+    let payload = &block_with_trailer[..split];
+    let trailer_meta = &block_with_trailer[split..split + 5];
+    let compression = trailer_meta[0];
+    let raw_len = u32::from_le_bytes(trailer_meta[1..5].try_into().unwrap()) as usize;
+
+    let mut digest_bytes = [0u8; 8];
+    digest_bytes[..digest_len].copy_from_slice(&block_with_trailer[split + 5..]);
+    let digest = u64::from_le_bytes(digest_bytes);
+    if !checksum::verify(options.checksum, payload, trailer_meta, digest) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "block checksum mismatch"));
+    }
+
+    if compression == crate::table::block_based::compression::NO_COMPRESSION {
+        return Ok(payload.to_vec());
+    }
+    let compressor = options.compressors.get(compression).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("no compressor registered for id {}", compression),
+        )
+    })?;
+    // `raw_len` is the exact uncompressed size recorded by the writer, so the
+    // compressor can size its output buffer precisely instead of guessing from the
+    // (possibly much smaller) compressed payload size.
+    compressor.decompress(payload, raw_len)
+}
+
+pub struct BlockBasedTableReader {
+    options: Arc<BlockBasedTableOptions>,
+}
+
+impl BlockBasedTableReader {
+    /// Builds a reader directly from `options` without consulting a table footer.
+    /// `options.checksum` must already match whatever the writer used — only
+    /// appropriate when the caller has that guarantee some other way (e.g. tests that
+    /// build and read a block in the same process). Prefer `open` otherwise.
+    pub fn new(options: Arc<BlockBasedTableOptions>) -> Self {
+        Self { options }
+    }
+
+    /// Opens a reader from a table's trailing `footer` bytes (as produced by
+    /// `BlockBasedTableBuilder::footer`), recovering the `ChecksumType` the writer
+    /// actually used instead of trusting `options.checksum` out of band. Reading with
+    /// the wrong checksum type would otherwise compute the wrong `digest_len` and
+    /// misparse every block's trailer.
+    pub fn open(mut options: BlockBasedTableOptions, footer: &[u8]) -> io::Result<Self> {
+        options.checksum = footer::decode(footer)?;
+        Ok(Self { options: Arc::new(options) })
+    }
+
+    pub fn read_block(&self, block_with_trailer: &[u8]) -> io::Result<Vec<u8>> {
+        read_block(block_with_trailer, &self.options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::block_based::options::BlockBasedTableOptions;
+    use crate::table::block_based::table_builder::compress_block;
+
+    #[test]
+    fn test_roundtrip_through_builder_and_reader() {
+        let options = BlockBasedTableOptions {
+            compression: crate::table::block_based::compression::ZSTD_COMPRESSION,
+            ..Default::default()
+        };
+        let raw = b"hello-world-hello-world-hello-world".repeat(4);
+        let written = compress_block(&raw, &options);
+        let read_back = read_block(&written, &options).unwrap();
+        assert_eq!(read_back, raw);
+    }
+
+    #[test]
+    fn test_full_pipeline_from_block_builder_to_decoded_entries() {
+        // Exercises the real end-to-end path: `BlockBuilder::finish` -> table-level
+        // compression + checksum trailer -> decompression + checksum verification ->
+        // entry decoding, instead of each piece only ever calling itself.
+        use crate::table::block_based::block::decode_entries;
+        use crate::table::block_based::block_builder::BlockBuilder;
+        use crate::table::block_based::options::DataBlockIndexType;
+
+        let options = BlockBasedTableOptions {
+            compression: crate::table::block_based::compression::ZSTD_COMPRESSION,
+            ..Default::default()
+        };
+        let mut builder =
+            BlockBuilder::new(2, true, DataBlockIndexType::DataBlockBinarySearch, 0.0);
+        let kvs: Vec<(&[u8], &[u8])> = vec![
+            (b"abcde001", b"v0"),
+            (b"abcde002", b"v1"),
+            (b"abcde003", b"v2"),
+            (b"abcde004", b"v3"),
+        ];
+        for (k, v) in kvs.iter() {
+            builder.add(k, v);
+        }
+        let raw_block = builder.finish();
+
+        let written = compress_block(&raw_block, &options);
+        let decompressed = read_block(&written, &options).unwrap();
+        let decoded = decode_entries(&decompressed);
+
+        assert_eq!(decoded.len(), kvs.len());
+        for ((key, value), (expected_key, expected_value)) in decoded.iter().zip(kvs.iter()) {
+            assert_eq!(key.as_slice(), *expected_key);
+            assert_eq!(value.as_slice(), *expected_value);
+        }
+    }
+
+    #[test]
+    fn test_corrupt_block_is_rejected() {
+        let options = BlockBasedTableOptions::default();
+        let mut written = compress_block(b"abc", &options);
+        let last = written.len() - 1;
+        written[last] ^= 0xff;
+        assert!(read_block(&written, &options).is_err());
+    }
+
+    #[test]
+    fn test_corrupt_raw_len_is_rejected() {
+        // `raw_len` sizes the decompression buffer, so it must be covered by the
+        // checksum: flipping it alone (payload and digest untouched) should fail
+        // verification rather than reach the compressor with a bogus hint.
+        let options = BlockBasedTableOptions {
+            compression: crate::table::block_based::compression::SNAPPY_COMPRESSION,
+            ..Default::default()
+        };
+        let mut written = compress_block(b"abcdefgh".repeat(8).as_slice(), &options);
+        let digest_len = options.checksum.digest_len();
+        let raw_len_offset = written.len() - digest_len - 4;
+        written[raw_len_offset] ^= 0xff;
+        assert!(read_block(&written, &options).is_err());
+    }
+
+    #[test]
+    fn test_highly_compressible_block_round_trips() {
+        // Regression test: `read_block` used to guess the decompression buffer size as
+        // `payload.len() * 4`, which is far too small once a block compresses better
+        // than 4:1 — a completely ordinary outcome for repetitive data like this.
+        let raw = b"abcdefgh-abcdefgh-abcdefgh-abcdefgh".repeat(50);
+        for compression in [
+            crate::table::block_based::compression::SNAPPY_COMPRESSION,
+            crate::table::block_based::compression::ZLIB_COMPRESSION,
+            crate::table::block_based::compression::LZ4_COMPRESSION,
+            crate::table::block_based::compression::ZSTD_COMPRESSION,
+        ] {
+            let options = BlockBasedTableOptions {
+                compression,
+                ..Default::default()
+            };
+            let written = compress_block(&raw, &options);
+            assert!(written.len() < raw.len() / 4);
+            assert_eq!(read_block(&written, &options).unwrap(), raw);
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_for_every_checksum_type() {
+        use crate::util::checksum::ChecksumType;
+
+        for checksum in [
+            ChecksumType::NoChecksum,
+            ChecksumType::CRC32c,
+            ChecksumType::XXHash,
+            ChecksumType::XXH3,
+        ] {
+            let options = BlockBasedTableOptions {
+                checksum,
+                ..Default::default()
+            };
+            let raw = b"checksum-roundtrip-payload".to_vec();
+            let written = compress_block(&raw, &options);
+            assert_eq!(read_block(&written, &options).unwrap(), raw);
+        }
+    }
+
+    #[test]
+    fn test_open_recovers_checksum_type_from_footer_not_reader_options() {
+        use crate::table::block_based::table_builder::BlockBasedTableBuilder;
+        use crate::util::checksum::ChecksumType;
+
+        let writer_options = Arc::new(BlockBasedTableOptions {
+            checksum: ChecksumType::XXH3,
+            ..Default::default()
+        });
+        let builder = BlockBasedTableBuilder::new(writer_options.clone());
+        let raw = b"footer-driven-checksum-recovery".to_vec();
+        let written = builder.finish_block(&raw);
+        let footer = builder.footer();
+
+        // The reader's own options still say CRC32c (the default); `open` must pull
+        // the real algorithm out of the footer instead of trusting that.
+        let reader_options = BlockBasedTableOptions::default();
+        assert_eq!(reader_options.checksum, ChecksumType::CRC32c);
+        let reader = BlockBasedTableReader::open(reader_options, &footer).unwrap();
+        assert_eq!(reader.read_block(&written).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_open_rejects_unknown_footer() {
+        let options = BlockBasedTableOptions::default();
+        assert!(BlockBasedTableReader::open(options, &[0xff]).is_err());
+    }
+}
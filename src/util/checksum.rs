@@ -0,0 +1,98 @@
+use crate::util::{crc_mask, crc_unmask};
+
+/// Whole-block checksum algorithm, stored once per table (in the footer/properties)
+/// rather than per block, so every block in a table is verified the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumType {
+    NoChecksum = 0,
+    CRC32c = 1,
+    XXHash = 2,
+    XXH3 = 3,
+}
+
+impl ChecksumType {
+    pub fn from_u8(v: u8) -> Option<ChecksumType> {
+        match v {
+            0 => Some(ChecksumType::NoChecksum),
+            1 => Some(ChecksumType::CRC32c),
+            2 => Some(ChecksumType::XXHash),
+            3 => Some(ChecksumType::XXH3),
+            _ => None,
+        }
+    }
+
+    /// Width, in bytes, of the digest this algorithm produces in a block trailer.
+    pub fn digest_len(self) -> usize {
+        match self {
+            ChecksumType::NoChecksum => 0,
+            ChecksumType::CRC32c => 4,
+            ChecksumType::XXHash => 4,
+            ChecksumType::XXH3 => 8,
+        }
+    }
+}
+
+/// Computes the configured whole-block checksum over `block_bytes ++ trailer_meta`,
+/// where `trailer_meta` is every trailer byte written between the payload and the
+/// digest (the compression id and the raw-length field), so corruption anywhere in
+/// the trailer is caught rather than just corruption of the payload itself.
+/// CRC32c is still folded through `crc_mask` so tables written with this checksum type
+/// stay wire-compatible with the CRC32c block trailers used before other algorithms
+/// were supported.
+pub fn compute(checksum_type: ChecksumType, block_bytes: &[u8], trailer_meta: &[u8]) -> u64 {
+    match checksum_type {
+        ChecksumType::NoChecksum => 0,
+        ChecksumType::CRC32c => {
+            let crc = crc32c::crc32c_append(crc32c::crc32c(block_bytes), trailer_meta);
+            crc_mask(crc) as u64
+        }
+        ChecksumType::XXHash => {
+            let mut hasher = twox_hash::XxHash32::with_seed(0);
+            std::hash::Hasher::write(&mut hasher, block_bytes);
+            std::hash::Hasher::write(&mut hasher, trailer_meta);
+            std::hash::Hasher::finish(&hasher)
+        }
+        ChecksumType::XXH3 => {
+            let mut hasher = twox_hash::Xxh3Hash64::with_seed(0);
+            std::hash::Hasher::write(&mut hasher, block_bytes);
+            std::hash::Hasher::write(&mut hasher, trailer_meta);
+            std::hash::Hasher::finish(&hasher)
+        }
+    }
+}
+
+/// Recomputes the checksum over `block_bytes ++ trailer_meta` and compares it against
+/// `digest`, returning `false` on mismatch (corrupt block).
+pub fn verify(checksum_type: ChecksumType, block_bytes: &[u8], trailer_meta: &[u8], digest: u64) -> bool {
+    if checksum_type == ChecksumType::NoChecksum {
+        return true;
+    }
+    if checksum_type == ChecksumType::CRC32c {
+        // The stored digest is the masked CRC produced by `compute`; unmask it and
+        // compare against the freshly computed raw CRC.
+        let crc = crc32c::crc32c_append(crc32c::crc32c(block_bytes), trailer_meta);
+        return crc_unmask(digest as u32) == crc;
+    }
+    compute(checksum_type, block_bytes, trailer_meta) == digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_each_checksum_type_roundtrips() {
+        let data = b"the quick brown fox jumps over the lazy dog".repeat(3);
+        for checksum_type in [ChecksumType::CRC32c, ChecksumType::XXHash, ChecksumType::XXH3] {
+            let digest = compute(checksum_type, &data, &[1]);
+            assert!(verify(checksum_type, &data, &[1], digest));
+            assert!(!verify(checksum_type, &data, &[2], digest));
+        }
+    }
+
+    #[test]
+    fn test_no_checksum_always_verifies() {
+        let data = b"unchecked".to_vec();
+        assert!(verify(ChecksumType::NoChecksum, &data, &[0], 0xdeadbeef));
+    }
+}
@@ -1,6 +1,7 @@
 mod btree;
 mod cache;
 pub use cache::{CachableEntry, LRUCache};
+pub mod checksum;
 pub mod hash;
 mod test_sync_point;
 